@@ -10,13 +10,19 @@
 pub use iced;
 pub use plotters;
 
+mod recording;
+pub use recording::{RecordingBackend, RecordingState};
+
 use iced::canvas::{self, LineCap, LineJoin, Path, Stroke, Text};
-use iced::{Color, Point, Size};
+use iced::{Color, Font, HorizontalAlignment, Point, Size, VerticalAlignment};
 
 use plotters::{
     drawing::{backend::BackendStyle, DrawingBackend},
     prelude::backend::{BackendCoord, DrawingErrorKind},
-    style::{Color as ColorTrait, RGBAColor, TextStyle},
+    style::{
+        text_anchor::{HPos, VPos},
+        Color as ColorTrait, FontTransform, RGBAColor, TextStyle,
+    },
 };
 
 use std::error::Error;
@@ -29,12 +35,220 @@ pub trait Plottable: std::fmt::Debug {
 
 /// A wrapper around a canvas which can draw a plotters chart.
 #[derive(Debug)]
-pub struct PlotFrame<'a>(pub &'a mut canvas::Frame);
+pub struct PlotFrame<'a> {
+    /// The underlying canvas frame that plotters draws into.
+    pub frame: &'a mut canvas::Frame,
+    line_style: LineStyle,
+}
+
+impl<'a> PlotFrame<'a> {
+    /// Wraps a canvas frame so plotters can draw into it using a solid line style.
+    pub fn new(frame: &'a mut canvas::Frame) -> Self {
+        Self {
+            frame,
+            line_style: LineStyle::default(),
+        }
+    }
+
+    /// Returns this frame configured to stroke lines, rects, circles, and
+    /// paths with the given `line_style` instead of the default solid stroke.
+    pub fn with_line_style(mut self, line_style: LineStyle) -> Self {
+        self.line_style = line_style;
+        self
+    }
+
+    /// Builds the `iced` stroke used for every stroked primitive, applying
+    /// this frame's configured cap and join. This version of iced's `Stroke`
+    /// has no dash field of its own, so dashing is applied separately by
+    /// [`PlotFrame::stroke_polyline`], which breaks the path into dash/gap
+    /// segments before stroking each one with this style.
+    fn stroke_style(&self, color: Color, width: f32) -> Stroke {
+        Stroke {
+            color,
+            width,
+            line_cap: self.line_style.line_cap,
+            line_join: self.line_style.line_join,
+        }
+    }
+
+    /// Strokes the polyline through `points`, split into dash/gap segments
+    /// per this frame's configured [`LineStyle`] when a dash pattern is set,
+    /// or as one continuous stroke otherwise.
+    fn stroke_polyline(&mut self, points: &[Point], color: Color, width: f32) {
+        if points.len() < 2 {
+            return;
+        }
+
+        if self.line_style.dash.is_empty() {
+            let p = Path::new(|builder| {
+                builder.move_to(points[0]);
+                for point in &points[1..] {
+                    builder.line_to(*point);
+                }
+            });
+            self.frame.stroke(&p, self.stroke_style(color, width));
+            return;
+        }
+
+        for (from, to) in dash_segments(points, &self.line_style.dash, self.line_style.dash_offset)
+        {
+            let p = Path::line(from, to);
+            self.frame.stroke(&p, self.stroke_style(color, width));
+        }
+    }
+}
+
+/// Splits the polyline through `points` into the sub-segments that should be
+/// stroked "on", walking `dash`'s alternating on/off lengths starting
+/// `offset` logical pixels into the pattern.
+fn dash_segments(points: &[Point], dash: &[f32], offset: usize) -> Vec<(Point, Point)> {
+    let total: f32 = dash.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut segments = Vec::new();
+    let mut pattern_pos = offset as f32 % total;
+
+    for pair in points.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let seg_len = distance(start, end);
+        if seg_len <= f32::EPSILON {
+            continue;
+        }
+        let dir = ((end.x - start.x) / seg_len, (end.y - start.y) / seg_len);
+
+        let mut cursor = start;
+        let mut remaining = seg_len;
+        while remaining > 0.0 {
+            let (dash_index, dash_remaining) = dash_position(pattern_pos, dash);
+            let step = dash_remaining.min(remaining);
+            if step <= 0.0 {
+                break;
+            }
+            let next = Point::new(cursor.x + dir.0 * step, cursor.y + dir.1 * step);
+            if dash_index % 2 == 0 {
+                segments.push((cursor, next));
+            }
+            cursor = next;
+            remaining -= step;
+            pattern_pos = (pattern_pos + step) % total;
+        }
+    }
+
+    segments
+}
+
+/// Finds which entry of `dash` contains `pattern_pos`, and how much of that
+/// entry remains before the pattern advances to the next one.
+fn dash_position(pattern_pos: f32, dash: &[f32]) -> (usize, f32) {
+    let mut consumed = 0.0;
+    for (index, len) in dash.iter().enumerate() {
+        if pattern_pos < consumed + len {
+            return (index, consumed + len - pattern_pos);
+        }
+        consumed += len;
+    }
+    (dash.len() - 1, 0.0)
+}
+
+/// Straight-line distance between two points.
+fn distance(a: Point, b: Point) -> f32 {
+    ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt()
+}
+
+/// Number of segments used to approximate a circle's outline as a closed
+/// polyline, so a dashed [`LineStyle`] can be applied to it through
+/// [`PlotFrame::stroke_polyline`] the same way as straight-edged primitives.
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// Approximates the outline of a circle centered at `center` with the given
+/// `radius` as a closed polyline.
+fn circle_polygon(center: Point, radius: f32) -> Vec<Point> {
+    (0..=CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = (i as f32 / CIRCLE_SEGMENTS as f32) * std::f32::consts::TAU;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Decides how `draw_circle` should stroke a hollow circle given `line_style`:
+/// `None` to stroke the circle natively as a solid arc, or `Some` with the
+/// polygon approximation to split into dash segments when `line_style` has a
+/// dash pattern.
+fn circle_outline_for_stroke(
+    line_style: &LineStyle,
+    center: Point,
+    radius: f32,
+) -> Option<Vec<Point>> {
+    if line_style.dash.is_empty() {
+        None
+    } else {
+        Some(circle_polygon(center, radius))
+    }
+}
+
+/// Stroke configuration applied to every stroked primitive drawn through a
+/// [`PlotFrame`], letting plotters' gridlines, reference lines, and dashed
+/// series render with something other than a solid butt-capped line. Circle
+/// outlines honor the dash pattern too, approximated as a polygon so they can
+/// be split into dash segments like any other primitive.
+#[derive(Debug, Clone)]
+pub struct LineStyle {
+    /// Alternating dash/gap lengths, in logical pixels. An empty slice draws
+    /// a solid line.
+    pub dash: Vec<f32>,
+    /// Offset into the dash pattern, in logical pixels.
+    pub dash_offset: usize,
+    /// How the ends of open strokes are drawn.
+    pub line_cap: LineCap,
+    /// How stroke segments are joined.
+    pub line_join: LineJoin,
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        Self {
+            dash: Vec::new(),
+            dash_offset: 0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+        }
+    }
+}
+
+/// Converts a plotters RGBA color into an iced color, preserving alpha.
+fn to_iced_color(c: &RGBAColor) -> Color {
+    let (r, g, b) = c.rgb();
+    Color::from_rgba8(r, g, b, c.alpha() as f32)
+}
+
+/// Maps a plotters horizontal text anchor onto its iced equivalent.
+fn to_horizontal_alignment(pos: HPos) -> HorizontalAlignment {
+    match pos {
+        HPos::Left => HorizontalAlignment::Left,
+        HPos::Right => HorizontalAlignment::Right,
+        HPos::Center => HorizontalAlignment::Center,
+    }
+}
+
+/// Maps a plotters vertical text anchor onto its iced equivalent.
+fn to_vertical_alignment(pos: VPos) -> VerticalAlignment {
+    match pos {
+        VPos::Top => VerticalAlignment::Top,
+        VPos::Center => VerticalAlignment::Center,
+        VPos::Bottom => VerticalAlignment::Bottom,
+    }
+}
 
 impl<'a> DrawingBackend for PlotFrame<'a> {
     type ErrorType = PlotErr;
     fn get_size(&self) -> (u32, u32) {
-        (self.0.height() as u32, self.0.width() as u32)
+        (self.frame.height() as u32, self.frame.width() as u32)
     }
 
     fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
@@ -54,8 +268,7 @@ impl<'a> DrawingBackend for PlotFrame<'a> {
             Point::new(point.0 as f32, point.1 as f32),
             Size::new(0.6, 0.6),
         );
-        let (r, g, b) = color.rgb();
-        self.0.fill(&p, Color::from_rgb8(r, g, b));
+        self.frame.fill(&p, to_iced_color(color));
         Ok(())
     }
 
@@ -65,18 +278,15 @@ impl<'a> DrawingBackend for PlotFrame<'a> {
         to: BackendCoord,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let p = Path::line(
+        let points = [
             Point::new(from.0 as f32, from.1 as f32),
             Point::new(to.0 as f32, to.1 as f32),
+        ];
+        self.stroke_polyline(
+            &points,
+            to_iced_color(&style.as_color()),
+            style.stroke_width() as f32,
         );
-        let (r, g, b) = style.as_color().rgb();
-        let stroke = Stroke {
-            color: Color::from_rgb8(r, g, b),
-            width: style.stroke_width() as f32,
-            line_cap: LineCap::Butt,
-            line_join: LineJoin::Miter,
-        };
-        self.0.stroke(&p, stroke);
         Ok(())
     }
 
@@ -87,26 +297,26 @@ impl<'a> DrawingBackend for PlotFrame<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let p = Path::rectangle(
-            Point::new(bottom_right.0 as f32, bottom_right.1 as f32),
-            Size::new(
-                (upper_left.0 - bottom_right.0) as f32,
-                (upper_left.1 - bottom_right.1) as f32,
-            ),
-        );
-        let (r, g, b) = style.as_color().rgb();
-        let color = Color::from_rgb8(r, g, b);
+        let color = to_iced_color(&style.as_color());
         if fill {
-            self.0.fill(&p, color);
+            let p = Path::rectangle(
+                Point::new(bottom_right.0 as f32, bottom_right.1 as f32),
+                Size::new(
+                    (upper_left.0 - bottom_right.0) as f32,
+                    (upper_left.1 - bottom_right.1) as f32,
+                ),
+            );
+            self.frame.fill(&p, color);
         } else {
-            let stroke = Stroke {
+            let top_left = Point::new(upper_left.0 as f32, upper_left.1 as f32);
+            let top_right = Point::new(bottom_right.0 as f32, upper_left.1 as f32);
+            let bottom_right = Point::new(bottom_right.0 as f32, bottom_right.1 as f32);
+            let bottom_left = Point::new(upper_left.0 as f32, bottom_right.y);
+            self.stroke_polyline(
+                &[top_left, top_right, bottom_right, bottom_left, top_left],
                 color,
-                width: style.stroke_width() as f32,
-                line_cap: LineCap::Butt,
-                line_join: LineJoin::Miter,
-            };
-
-            self.0.stroke(&p, stroke);
+                style.stroke_width() as f32,
+            );
         }
         Ok(())
     }
@@ -118,20 +328,23 @@ impl<'a> DrawingBackend for PlotFrame<'a> {
         style: &S,
         fill: bool,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let p = Path::circle(Point::new(center.0 as f32, center.1 as f32), radius as f32);
-        let (r, g, b) = style.as_color().rgb();
-        let color = Color::from_rgb8(r, g, b);
+        let center_point = Point::new(center.0 as f32, center.1 as f32);
+        let color = to_iced_color(&style.as_color());
 
         if fill {
-            self.0.fill(&p, color);
+            let p = Path::circle(center_point, radius as f32);
+            self.frame.fill(&p, color);
+        } else if let Some(outline) =
+            circle_outline_for_stroke(&self.line_style, center_point, radius as f32)
+        {
+            self.stroke_polyline(&outline, color, style.stroke_width() as f32);
         } else {
-            let stroke = Stroke {
-                color,
-                width: style.stroke_width() as f32,
-                line_cap: LineCap::Butt,
-                line_join: LineJoin::Miter,
-            };
-            self.0.stroke(&p, stroke);
+            // Circular strokes are solid unless a dash pattern needs the
+            // polygon approximation above, since a circle isn't a polyline
+            // to split into dash segments on its own.
+            let p = Path::circle(center_point, radius as f32);
+            self.frame
+                .stroke(&p, self.stroke_style(color, style.stroke_width() as f32));
         }
         Ok(())
     }
@@ -142,15 +355,82 @@ impl<'a> DrawingBackend for PlotFrame<'a> {
         style: &TextStyle<'_>,
         pos: BackendCoord,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let (r, g, b) = style.color.rgb();
-
-        self.0.fill_text(Text {
+        let position = Point::new(pos.0 as f32, pos.1 as f32);
+        let text_primitive = Text {
             content: text.into(),
             size: style.font.get_size() as f32,
-            position: Point::new(pos.0 as f32, pos.1 as f32),
-            color: Color::from_rgb8(r, g, b),
+            position: if matches!(style.font.get_transform(), FontTransform::None) {
+                position
+            } else {
+                Point::ORIGIN
+            },
+            color: to_iced_color(&style.color),
+            horizontal_alignment: to_horizontal_alignment(style.pos.h_pos),
+            vertical_alignment: to_vertical_alignment(style.pos.v_pos),
+            // `iced::Font` in this version only supports the built-in
+            // `Default` font or an embedded `External` one; there is no
+            // runtime mapping from a plotters family/style name onto either,
+            // so we always fall back to the system default.
+            font: Font::Default,
             ..Text::default()
+        };
+
+        match style.font.get_transform() {
+            FontTransform::None => self.frame.fill_text(text_primitive),
+            FontTransform::Rotate90 => self.frame.with_save(|frame| {
+                frame.translate(iced::Vector::new(position.x, position.y));
+                frame.rotate(std::f32::consts::FRAC_PI_2);
+                frame.fill_text(text_primitive);
+            }),
+            FontTransform::Rotate180 => self.frame.with_save(|frame| {
+                frame.translate(iced::Vector::new(position.x, position.y));
+                frame.rotate(std::f32::consts::PI);
+                frame.fill_text(text_primitive);
+            }),
+            FontTransform::Rotate270 => self.frame.with_save(|frame| {
+                frame.translate(iced::Vector::new(position.x, position.y));
+                frame.rotate(-std::f32::consts::FRAC_PI_2);
+                frame.fill_text(text_primitive);
+            }),
+        }
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let points: Vec<Point> = path
+            .into_iter()
+            .map(|p| Point::new(p.0 as f32, p.1 as f32))
+            .collect();
+        self.stroke_polyline(
+            &points,
+            to_iced_color(&style.as_color()),
+            style.stroke_width() as f32,
+        );
+        Ok(())
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let mut points = vert.into_iter();
+        let first = match points.next() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let p = Path::new(|builder| {
+            builder.move_to(Point::new(first.0 as f32, first.1 as f32));
+            for point in points {
+                builder.line_to(Point::new(point.0 as f32, point.1 as f32));
+            }
+            builder.close();
         });
+        self.frame.fill(&p, to_iced_color(&style.as_color()));
         Ok(())
     }
 }
@@ -165,4 +445,158 @@ impl std::fmt::Display for PlotErr {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_iced_color_preserves_alpha() {
+        let color = to_iced_color(&RGBAColor(10, 20, 30, 0.5));
+        assert_eq!(color.r, 10.0 / 255.0);
+        assert_eq!(color.g, 20.0 / 255.0);
+        assert_eq!(color.b, 30.0 / 255.0);
+        assert_eq!(color.a, 0.5);
+    }
+
+    #[test]
+    fn horizontal_alignment_mapping() {
+        assert!(matches!(
+            to_horizontal_alignment(HPos::Left),
+            HorizontalAlignment::Left
+        ));
+        assert!(matches!(
+            to_horizontal_alignment(HPos::Center),
+            HorizontalAlignment::Center
+        ));
+        assert!(matches!(
+            to_horizontal_alignment(HPos::Right),
+            HorizontalAlignment::Right
+        ));
+    }
+
+    #[test]
+    fn vertical_alignment_mapping() {
+        assert!(matches!(
+            to_vertical_alignment(VPos::Top),
+            VerticalAlignment::Top
+        ));
+        assert!(matches!(
+            to_vertical_alignment(VPos::Center),
+            VerticalAlignment::Center
+        ));
+        assert!(matches!(
+            to_vertical_alignment(VPos::Bottom),
+            VerticalAlignment::Bottom
+        ));
+    }
+
+    #[test]
+    fn draw_text_applies_every_rotation_transform() {
+        // Regression coverage for the with_save/translate/rotate branches
+        // draw_text dispatches on, driven through a real headless
+        // iced::canvas::Frame for every FontTransform variant. Frame doesn't
+        // expose its recorded geometry, so this only guards against a
+        // transform no longer being handled (e.g. panicking, or a missing
+        // match arm), but that's exactly the kind of mistake this logic has
+        // no other coverage against.
+        use plotters::style::IntoFont;
+
+        for transform in [
+            FontTransform::None,
+            FontTransform::Rotate90,
+            FontTransform::Rotate180,
+            FontTransform::Rotate270,
+        ] {
+            let mut frame = canvas::Frame::new(Size::new(100.0, 100.0));
+            let style = ("sans-serif", 20)
+                .into_font()
+                .transform(transform)
+                .color(&RGBAColor(0, 0, 0, 1.0));
+
+            PlotFrame::new(&mut frame)
+                .draw_text("label", &style, (10, 10))
+                .expect("failed to draw text with a rotation transform");
+        }
+    }
+
+    #[test]
+    fn dash_position_walks_the_pattern() {
+        let dash = [4.0, 2.0];
+        assert_eq!(dash_position(0.0, &dash), (0, 4.0));
+        assert_eq!(dash_position(2.0, &dash), (0, 2.0));
+        assert_eq!(dash_position(5.0, &dash), (1, 1.0));
+    }
+
+    #[test]
+    fn dash_segments_splits_on_off_pattern() {
+        // [on=4, off=2] over a 10-long line starting at the pattern's
+        // beginning: on for [0, 4), off for [4, 6), on again for [6, 10].
+        let points = [Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+        let segments = dash_segments(&points, &[4.0, 2.0], 0);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[0].0.x, segments[0].1.x), (0.0, 4.0));
+        assert_eq!((segments[1].0.x, segments[1].1.x), (6.0, 10.0));
+    }
+
+    #[test]
+    fn dash_segments_honors_offset_wrap_around() {
+        // Same pattern, but starting 5 logical pixels into it (one short of
+        // wrapping back to "on"): the first "on" run is shifted to [1, 5),
+        // not the [0, 4) the unshifted pattern would produce.
+        let points = [Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+        let segments = dash_segments(&points, &[4.0, 2.0], 5);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[0].0.x, segments[0].1.x), (1.0, 5.0));
+        assert_eq!((segments[1].0.x, segments[1].1.x), (7.0, 10.0));
+    }
+
+    #[test]
+    fn circle_outline_for_stroke_is_native_when_undashed() {
+        let style = LineStyle::default();
+        assert!(circle_outline_for_stroke(&style, Point::new(0.0, 0.0), 10.0).is_none());
+    }
+
+    #[test]
+    fn circle_outline_for_stroke_approximates_polygon_when_dashed() {
+        let style = LineStyle {
+            dash: vec![4.0, 2.0],
+            ..LineStyle::default()
+        };
+        let outline = circle_outline_for_stroke(&style, Point::new(0.0, 0.0), 10.0)
+            .expect("a dash pattern should approximate the circle as a polygon");
+
+        assert_eq!(outline.len(), CIRCLE_SEGMENTS + 1);
+        assert_eq!((outline[0].x, outline[0].y), (10.0, 0.0));
+    }
+
+    #[test]
+    fn draw_circle_strokes_both_dashed_and_undashed_hollow_circles() {
+        // Regression test for the two follow-up fixes this branch already
+        // needed (f8ead8f, b288d1a): drive `PlotFrame::draw_circle` itself
+        // through a real, headless `iced::canvas::Frame` for both styles so
+        // a third regression in the branch selection has to fail a test,
+        // not just slip past the pure `circle_outline_for_stroke` checks
+        // above. `Frame` doesn't expose its recorded geometry, so this only
+        // asserts the call completes for both styles; the branch-selection
+        // logic itself is covered above.
+        let red = RGBAColor(255, 0, 0, 1.0);
+
+        let mut solid_frame = canvas::Frame::new(Size::new(100.0, 100.0));
+        PlotFrame::new(&mut solid_frame)
+            .draw_circle((50, 50), 20, &red, false)
+            .expect("failed to stroke an undashed hollow circle");
+
+        let mut dashed_frame = canvas::Frame::new(Size::new(100.0, 100.0));
+        PlotFrame::new(&mut dashed_frame)
+            .with_line_style(LineStyle {
+                dash: vec![4.0, 2.0],
+                ..LineStyle::default()
+            })
+            .draw_circle((50, 50), 20, &red, false)
+            .expect("failed to stroke a dashed hollow circle");
+    }
+}
+
 impl Error for PlotErr {}
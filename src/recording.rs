@@ -0,0 +1,350 @@
+//! A [`DrawingBackend`] that records the calls plotters makes instead of
+//! rendering them, so a [`Plottable`](crate::Plottable) implementation's
+//! drawing logic can be exercised in a `#[test]` without a live `iced`
+//! canvas, by driving plotters against a [`RecordingBackend`] directly
+//! rather than a [`PlotFrame`](crate::PlotFrame).
+//!
+//! ```
+//! use iced_plotters::{RecordingBackend, RecordingState};
+//! use plotters::prelude::*;
+//!
+//! let mut state = RecordingState::new(640, 480);
+//! {
+//!     let root = RecordingBackend::new(&mut state).into_drawing_area();
+//!     let mut chart = ChartBuilder::on(&root)
+//!         .build_cartesian_2d(0..10, 0..10)
+//!         .unwrap();
+//!     chart
+//!         .draw_series(LineSeries::new((0..10).map(|x| (x, x)), &RED))
+//!         .unwrap();
+//! }
+//! assert!(state.num_draw_path_call() > 0);
+//! ```
+
+use plotters::{
+    drawing::{backend::BackendStyle, DrawingBackend},
+    prelude::backend::{BackendCoord, DrawingErrorKind},
+    style::{RGBAColor, TextStyle},
+};
+
+use crate::PlotErr;
+
+/// The draw calls recorded by a [`RecordingBackend`], kept in a separate,
+/// independently owned value so they can still be inspected once the
+/// backend borrowing them has been handed off to plotters and dropped.
+#[derive(Debug, Default)]
+pub struct RecordingState {
+    size: (u32, u32),
+    draw_pixel_calls: Vec<(RGBAColor, BackendCoord)>,
+    draw_line_calls: Vec<(RGBAColor, BackendCoord, BackendCoord, u32)>,
+    draw_rect_calls: Vec<(RGBAColor, BackendCoord, BackendCoord, u32, bool)>,
+    draw_circle_calls: Vec<(RGBAColor, BackendCoord, u32, u32, bool)>,
+    draw_text_calls: Vec<(RGBAColor, String, BackendCoord)>,
+    draw_path_calls: Vec<(RGBAColor, Vec<BackendCoord>, u32)>,
+    fill_polygon_calls: Vec<(RGBAColor, Vec<BackendCoord>)>,
+}
+
+impl RecordingState {
+    /// Creates an empty recording state reporting `(width, height)` as its
+    /// canvas size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: (width, height),
+            ..Self::default()
+        }
+    }
+
+    /// The recorded `(color, point)` passed to every `draw_pixel` call.
+    pub fn draw_pixel_calls(&self) -> &[(RGBAColor, BackendCoord)] {
+        &self.draw_pixel_calls
+    }
+
+    /// The number of times `draw_pixel` was called.
+    pub fn num_draw_pixel_call(&self) -> usize {
+        self.draw_pixel_calls.len()
+    }
+
+    /// The recorded `(color, from, to, stroke_width)` passed to every
+    /// `draw_line` call.
+    pub fn draw_line_calls(&self) -> &[(RGBAColor, BackendCoord, BackendCoord, u32)] {
+        &self.draw_line_calls
+    }
+
+    /// The number of times `draw_line` was called.
+    pub fn num_draw_line_call(&self) -> usize {
+        self.draw_line_calls.len()
+    }
+
+    /// Returns `true` if any recorded `draw_line` call satisfies `f`.
+    pub fn assert_draw_line(
+        &self,
+        f: impl Fn(&RGBAColor, BackendCoord, BackendCoord, u32) -> bool,
+    ) -> bool {
+        self.draw_line_calls
+            .iter()
+            .any(|(color, from, to, width)| f(color, *from, *to, *width))
+    }
+
+    /// The recorded `(color, upper_left, bottom_right, stroke_width, fill)`
+    /// passed to every `draw_rect` call.
+    pub fn draw_rect_calls(&self) -> &[(RGBAColor, BackendCoord, BackendCoord, u32, bool)] {
+        &self.draw_rect_calls
+    }
+
+    /// The number of times `draw_rect` was called.
+    pub fn num_draw_rect_call(&self) -> usize {
+        self.draw_rect_calls.len()
+    }
+
+    /// Returns `true` if any recorded `draw_rect` call satisfies `f`.
+    pub fn assert_draw_rect(
+        &self,
+        f: impl Fn(&RGBAColor, BackendCoord, BackendCoord, u32, bool) -> bool,
+    ) -> bool {
+        self.draw_rect_calls
+            .iter()
+            .any(|(color, ul, br, width, fill)| f(color, *ul, *br, *width, *fill))
+    }
+
+    /// The recorded `(color, center, radius, stroke_width, fill)` passed to
+    /// every `draw_circle` call.
+    pub fn draw_circle_calls(&self) -> &[(RGBAColor, BackendCoord, u32, u32, bool)] {
+        &self.draw_circle_calls
+    }
+
+    /// The number of times `draw_circle` was called.
+    pub fn num_draw_circle_call(&self) -> usize {
+        self.draw_circle_calls.len()
+    }
+
+    /// Returns `true` if any recorded `draw_circle` call satisfies `f`.
+    pub fn assert_draw_circle(
+        &self,
+        f: impl Fn(&RGBAColor, BackendCoord, u32, u32, bool) -> bool,
+    ) -> bool {
+        self.draw_circle_calls
+            .iter()
+            .any(|(color, center, radius, width, fill)| f(color, *center, *radius, *width, *fill))
+    }
+
+    /// The recorded `(color, text, position)` passed to every `draw_text`
+    /// call.
+    pub fn draw_text_calls(&self) -> &[(RGBAColor, String, BackendCoord)] {
+        &self.draw_text_calls
+    }
+
+    /// The number of times `draw_text` was called.
+    pub fn num_draw_text_call(&self) -> usize {
+        self.draw_text_calls.len()
+    }
+
+    /// Returns `true` if any recorded `draw_text` call satisfies `f`.
+    pub fn assert_draw_text(&self, f: impl Fn(&RGBAColor, &str, BackendCoord) -> bool) -> bool {
+        self.draw_text_calls
+            .iter()
+            .any(|(color, text, pos)| f(color, text, *pos))
+    }
+
+    /// The recorded `(color, vertices, stroke_width)` passed to every
+    /// `draw_path` call.
+    pub fn draw_path_calls(&self) -> &[(RGBAColor, Vec<BackendCoord>, u32)] {
+        &self.draw_path_calls
+    }
+
+    /// The number of times `draw_path` was called.
+    pub fn num_draw_path_call(&self) -> usize {
+        self.draw_path_calls.len()
+    }
+
+    /// The recorded `(color, vertices)` passed to every `fill_polygon` call.
+    pub fn fill_polygon_calls(&self) -> &[(RGBAColor, Vec<BackendCoord>)] {
+        &self.fill_polygon_calls
+    }
+
+    /// The number of times `fill_polygon` was called.
+    pub fn num_fill_polygon_call(&self) -> usize {
+        self.fill_polygon_calls.len()
+    }
+}
+
+/// A `DrawingBackend` that records every draw call it receives into a
+/// [`RecordingState`] instead of rendering them, so a [`Plottable`] impl can
+/// be unit-tested without a live canvas. Mirrors [`PlotFrame`]'s borrow of an
+/// externally owned value, so the recorded calls remain readable through
+/// `state` after the backend itself has been consumed by plotters.
+///
+/// [`PlotFrame`]: crate::PlotFrame
+#[derive(Debug)]
+pub struct RecordingBackend<'a>(&'a mut RecordingState);
+
+impl<'a> RecordingBackend<'a> {
+    /// Wraps `state` so plotters can record draw calls into it.
+    pub fn new(state: &'a mut RecordingState) -> Self {
+        Self(state)
+    }
+}
+
+impl<'a> DrawingBackend for RecordingBackend<'a> {
+    type ErrorType = PlotErr;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.0.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        Ok(())
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: &RGBAColor,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0.draw_pixel_calls.push((color.clone(), point));
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0
+            .draw_line_calls
+            .push((style.as_color(), from, to, style.stroke_width()));
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0.draw_rect_calls.push((
+            style.as_color(),
+            upper_left,
+            bottom_right,
+            style.stroke_width(),
+            fill,
+        ));
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0
+            .draw_circle_calls
+            .push((style.as_color(), center, radius, style.stroke_width(), fill));
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        text: &str,
+        style: &TextStyle<'_>,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0
+            .draw_text_calls
+            .push((style.color.clone(), text.to_owned(), pos));
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0.draw_path_calls.push((
+            style.as_color(),
+            path.into_iter().collect(),
+            style.stroke_width(),
+        ));
+        Ok(())
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.0
+            .fill_polygon_calls
+            .push((style.as_color(), vert.into_iter().collect()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use plotters::{prelude::*, style::Color as ColorTrait};
+
+    #[test]
+    fn records_chart_draw_calls() {
+        let mut state = RecordingState::new(200, 100);
+        {
+            let root = RecordingBackend::new(&mut state).into_drawing_area();
+            let mut chart = ChartBuilder::on(&root)
+                .margin(5)
+                .build_cartesian_2d(0..10, 0..10)
+                .expect("failed to build chart");
+
+            chart
+                .draw_series(LineSeries::new((0..10).map(|x| (x, x)), &RED))
+                .expect("failed to draw line series");
+        }
+
+        assert!(state.num_draw_path_call() > 0);
+        assert!(state
+            .draw_path_calls()
+            .iter()
+            .any(|(color, _, _)| color.rgb() == (255, 0, 0)));
+    }
+
+    #[test]
+    fn records_translucent_path_and_polygon_calls() {
+        let mut state = RecordingState::new(200, 100);
+        let mut backend = RecordingBackend::new(&mut state);
+
+        // Two "on" segments of a dashed line, each sent as its own
+        // `draw_path` call, plus a fill, all with a translucent style whose
+        // alpha should be recorded untouched.
+        let translucent = RGBAColor(0, 200, 0, 0.25);
+        backend
+            .draw_path(vec![(0, 0), (4, 0)], &translucent)
+            .expect("failed to draw first dash segment");
+        backend
+            .draw_path(vec![(8, 0), (12, 0)], &translucent)
+            .expect("failed to draw second dash segment");
+        backend
+            .fill_polygon(vec![(0, 0), (12, 0), (12, 6), (0, 6)], &translucent)
+            .expect("failed to fill polygon");
+
+        assert_eq!(
+            state.num_draw_path_call(),
+            2,
+            "expected one recorded draw_path call per dash segment"
+        );
+        for (color, _, _) in state.draw_path_calls() {
+            assert_eq!(color.alpha(), 0.25);
+        }
+
+        assert_eq!(state.num_fill_polygon_call(), 1);
+        let (fill_color, vertices) = &state.fill_polygon_calls()[0];
+        assert_eq!(fill_color.alpha(), 0.25);
+        assert_eq!(vertices.len(), 4);
+    }
+}